@@ -0,0 +1,71 @@
+use std::any::{Any, TypeId};
+
+use anyhow::{bail, Result};
+
+use crate::{ReadRef, TraitRef, VcValueTrait, VcValueType};
+
+/// A type-erased, reference-counted value stored in a cell, tagged with the
+/// `TypeId` of the concrete [`VcValueType`] it was written with.
+#[derive(Clone)]
+pub(crate) struct SharedReference(pub TypeId, pub std::sync::Arc<dyn Any + Send + Sync>);
+
+/// The content of a single cell in the task graph: either empty, or a
+/// [`SharedReference`] pointing at the value the cell was last written with.
+#[derive(Clone)]
+pub struct CellContent(pub(crate) Option<SharedReference>);
+
+impl CellContent {
+    /// Casts this cell's content into a concrete value, erroring if the cell
+    /// is empty or doesn't hold a `T`.
+    pub fn cast<T>(self) -> Result<ReadRef<T>>
+    where
+        T: VcValueType,
+    {
+        let Some(SharedReference(ty, value)) = self.0 else {
+            bail!("Cell is empty");
+        };
+        if ty != TypeId::of::<T>() {
+            bail!("Unexpected type in cell");
+        }
+        let value = value
+            .downcast::<T>()
+            .map_err(|_| anyhow::anyhow!("Unexpected type in cell"))?;
+        Ok(ReadRef::new(value))
+    }
+
+    /// Casts this cell's content into a [`TraitRef<T>`].
+    ///
+    /// # Safety
+    ///
+    /// Constructor ensures the cell content points to a value that
+    /// implements `T`.
+    pub fn cast_trait<T>(self) -> Result<TraitRef<T>>
+    where
+        T: VcValueTrait + ?Sized,
+    {
+        match self.try_cast_trait::<T>()? {
+            Some(trait_ref) => Ok(trait_ref),
+            None => bail!(
+                "Cell content doesn't implement trait {}",
+                std::any::type_name::<T>()
+            ),
+        }
+    }
+
+    /// Fallibly casts this cell's content into a [`TraitRef<T>`]. Performs
+    /// the same type check as [`Self::cast_trait`], but returns `Ok(None)`
+    /// instead of erroring when the underlying value type doesn't implement
+    /// `T`.
+    pub fn try_cast_trait<T>(self) -> Result<Option<TraitRef<T>>>
+    where
+        T: VcValueTrait + ?Sized,
+    {
+        let Some(SharedReference(ty, _)) = &self.0 else {
+            bail!("Cell is empty");
+        };
+        if !T::has_impl(*ty) {
+            return Ok(None);
+        }
+        Ok(Some(TraitRef::new(self.0.unwrap())))
+    }
+}
@@ -0,0 +1,133 @@
+use std::{borrow::Borrow, fmt, ops::Deref, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+
+/// A cheaply cloneable, immutable string.
+///
+/// `#[turbo_tasks::function]` arguments and value fields that hold text
+/// should prefer `RcStr` over `String`: cloning an `RcStr` only bumps a
+/// reference count, so calling a memoized task repeatedly with the same
+/// textual input doesn't force a fresh heap allocation on every invocation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RcStr(Arc<str>);
+
+impl Deref for RcStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for RcStr {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for RcStr {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RcStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&*self.0, f)
+    }
+}
+
+impl From<String> for RcStr {
+    fn from(value: String) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<&str> for RcStr {
+    fn from(value: &str) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<Arc<str>> for RcStr {
+    fn from(value: Arc<str>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<RcStr> for String {
+    fn from(value: RcStr) -> Self {
+        value.0.to_string()
+    }
+}
+
+impl PartialEq<str> for RcStr {
+    fn eq(&self, other: &str) -> bool {
+        &*self.0 == other
+    }
+}
+
+impl PartialEq<&str> for RcStr {
+    fn eq(&self, other: &&str) -> bool {
+        &*self.0 == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn from_string_and_str_round_trip() {
+        let from_owned: RcStr = String::from("hello").into();
+        let from_borrowed: RcStr = "hello".into();
+
+        assert_eq!(from_owned, from_borrowed);
+        assert_eq!(String::from(from_owned), "hello");
+    }
+
+    #[test]
+    fn derefs_to_str() {
+        let value: RcStr = "hello world".into();
+
+        assert_eq!(value.len(), 11);
+        assert!(value.starts_with("hello"));
+    }
+
+    #[test]
+    fn compares_equal_to_str_and_ref_str() {
+        let value: RcStr = "hello".into();
+
+        assert_eq!(value, *"hello");
+        assert_eq!(value, "hello");
+    }
+
+    #[test]
+    fn clone_is_cheap_and_shares_equality() {
+        let value: RcStr = "shared".into();
+        let cloned = value.clone();
+
+        assert_eq!(value, cloned);
+        assert!(std::ptr::eq(value.as_ptr(), cloned.as_ptr()));
+    }
+
+    #[test]
+    fn hashes_and_orders_like_the_wrapped_str() {
+        let mut set = HashSet::new();
+        set.insert(RcStr::from("a"));
+        set.insert(RcStr::from("b"));
+        set.insert(RcStr::from("a"));
+
+        assert_eq!(set.len(), 2);
+        assert!(RcStr::from("a") < RcStr::from("b"));
+    }
+
+    #[test]
+    fn display_matches_the_wrapped_str() {
+        let value: RcStr = "display me".into();
+
+        assert_eq!(value.to_string(), "display me");
+    }
+}
@@ -50,3 +50,28 @@ where
         content.cast_trait::<T>()
     }
 }
+
+/// Fallibly casts an arbitrary cell content into a [`TraitRef<T>`], without
+/// relying on the constructor invariant [`VcValueTraitCast`] assumes.
+///
+/// Unlike [`VcValueTraitCast`], this never errors on a type mismatch: it
+/// returns `Ok(None)` when the cell's underlying value type does not
+/// implement `T`, so callers can probe a trait `Vc` for an additional trait
+/// and branch on the result instead of propagating an error.
+pub struct VcValueTraitTryCast<T>
+where
+    T: ?Sized,
+{
+    _phantom: PhantomData<T>,
+}
+
+impl<T> VcCast for VcValueTraitTryCast<T>
+where
+    T: VcValueTrait + ?Sized,
+{
+    type Output = Option<TraitRef<T>>;
+
+    fn cast(content: CellContent) -> Result<Self::Output> {
+        content.try_cast_trait::<T>()
+    }
+}
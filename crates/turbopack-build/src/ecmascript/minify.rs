@@ -4,7 +4,7 @@ use anyhow::{Context, Result};
 use swc_core::{
     base::{try_with_handler, Compiler},
     common::{
-        comments::{Comments, SingleThreadedComments},
+        comments::{Comment, Comments, SingleThreadedComments},
         BytePos, FileName, FilePathMapping, LineCol, Mark, SourceMap as SwcSourceMap, GLOBALS,
     },
     ecma::{
@@ -12,14 +12,14 @@ use swc_core::{
         ast::{EsVersion, Program},
         codegen::{
             text_writer::{self, JsWriter, WriteJs},
-            Emitter, Node,
+            Config as CodegenConfig, Emitter, Node,
         },
-        minifier::option::{ExtraOptions, MinifyOptions},
-        parser::{lexer::Lexer, Parser, StringInput, Syntax},
+        minifier::option::{CompressOptions, ExtraOptions, MangleOptions, MinifyOptions},
+        parser::{lexer::Lexer, EsSyntax, Parser, StringInput, Syntax, TsSyntax},
         visit::FoldWith,
     },
 };
-use turbo_tasks::Vc;
+use turbo_tasks::{RcStr, Vc};
 use turbo_tasks_fs::FileSystemPath;
 use turbopack_core::{
     code_builder::{Code, CodeBuilder},
@@ -27,10 +27,114 @@ use turbopack_core::{
 };
 use turbopack_ecmascript::ParseResultSourceMap;
 
+/// Controls which comments, if any, survive minification.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MinifyCommentsConfig {
+    /// Strip every comment (previous behavior).
+    #[default]
+    None,
+    /// Keep only legal/license comments: a leading `!`, or text containing
+    /// `@license`/`@preserve`.
+    Some,
+    /// Keep every comment.
+    All,
+}
+
+/// Tunable knobs for [`minify`]/[`perform_minify`], mapped onto swc's
+/// `MinifyOptions` compress/mangle sub-options plus an output target.
+///
+/// Defaults match the previous hardcoded behavior: mangle and compress both
+/// enabled with their swc defaults, and an `EsVersion::latest()` target.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Clone)]
+pub struct MinifyConfig {
+    /// Whether to rename local bindings to shorter names.
+    pub mangle: bool,
+    /// Top-level names that must survive mangling (e.g. an exported global).
+    pub keep_names: Vec<RcStr>,
+    /// Remove `console.*` calls.
+    pub drop_console: bool,
+    /// Remove `debugger` statements.
+    pub drop_debugger: bool,
+    /// Maximum number of compressor passes; `0` (swc's default) repeats
+    /// passes until the output stops changing.
+    pub compress_passes: usize,
+    /// ECMAScript version the emitted syntax must stay compatible with.
+    pub target: EsVersion,
+    /// Which comments to retain in the minified output.
+    pub comments: MinifyCommentsConfig,
+}
+
+impl Default for MinifyConfig {
+    fn default() -> Self {
+        Self {
+            mangle: true,
+            keep_names: Vec::new(),
+            drop_console: false,
+            drop_debugger: false,
+            compress_passes: 0,
+            target: EsVersion::latest(),
+            comments: MinifyCommentsConfig::None,
+        }
+    }
+}
+
+fn should_keep_comment(comment: &Comment, mode: MinifyCommentsConfig) -> bool {
+    match mode {
+        MinifyCommentsConfig::None => false,
+        MinifyCommentsConfig::All => true,
+        MinifyCommentsConfig::Some => {
+            comment.text.starts_with('!')
+                || comment.text.contains("@license")
+                || comment.text.contains("@preserve")
+        }
+    }
+}
+
+/// Builds a comments store containing only the comments `mode` allows to
+/// survive, so the [`Emitter`] can be handed a filtered view without mutating
+/// the original comments collected during parsing.
+fn filtered_comments(
+    comments: &SingleThreadedComments,
+    mode: MinifyCommentsConfig,
+) -> SingleThreadedComments {
+    let filtered = SingleThreadedComments::default();
+    if mode == MinifyCommentsConfig::None {
+        return filtered;
+    }
+    for (pos, list) in comments.leading_map().iter() {
+        for comment in list.iter().filter(|c| should_keep_comment(c, mode)) {
+            filtered.add_leading(*pos, comment.clone());
+        }
+    }
+    for (pos, list) in comments.trailing_map().iter() {
+        for comment in list.iter().filter(|c| should_keep_comment(c, mode)) {
+            filtered.add_trailing(*pos, comment.clone());
+        }
+    }
+    filtered
+}
+
+#[turbo_tasks::value_impl]
+impl MinifyConfig {
+    /// Named `vc_default` rather than `default` so it doesn't shadow
+    /// `<MinifyConfig as Default>::default()` with an inherent method of the
+    /// same name returning a different type.
+    #[turbo_tasks::function]
+    pub fn vc_default() -> Vc<Self> {
+        Self::cell(Default::default())
+    }
+}
+
 #[turbo_tasks::function]
-pub async fn minify(path: Vc<FileSystemPath>, code: Vc<Code>) -> Result<Vc<Code>> {
+pub async fn minify(
+    path: Vc<FileSystemPath>,
+    code: Vc<Code>,
+    config: Vc<MinifyConfig>,
+) -> Result<Vc<Code>> {
     let original_map = *code.generate_source_map().await?;
-    let minified_code = perform_minify(path, code);
+    let minified_code = perform_minify(path, code, config);
 
     let merged = match (original_map, *minified_code.generate_source_map().await?) {
         (Some(original_map), Some(minify_map)) => Some(Vc::upcast(original_map.trace(minify_map))),
@@ -46,26 +150,68 @@ pub async fn minify(path: Vc<FileSystemPath>, code: Vc<Code>) -> Result<Vc<Code>
 }
 
 #[turbo_tasks::function]
-async fn perform_minify(path: Vc<FileSystemPath>, code_vc: Vc<Code>) -> Result<Vc<Code>> {
+async fn perform_minify(
+    path: Vc<FileSystemPath>,
+    code_vc: Vc<Code>,
+    config: Vc<MinifyConfig>,
+) -> Result<Vc<Code>> {
     let code = &*code_vc.await?;
+    let config = &*config.await?;
+    let filename = FileName::Custom((*path.await?.path).to_string());
+    let source = code.source_code().to_str()?.to_string();
+
+    let MinifyResult {
+        source: src,
+        cm,
+        src_map_buf,
+    } = minify_source(filename, source, config)?;
+
+    let mut builder = CodeBuilder::default();
+    builder.push_source(
+        &src.into(),
+        Some(*Box::new(Vc::upcast(
+            ParseResultSourceMap::new(cm, src_map_buf).cell(),
+        ))),
+    );
+
+    Ok(builder.build().cell())
+}
+
+/// Output of [`minify_source`]: the minified text plus everything needed to
+/// build a source map for it.
+pub(crate) struct MinifyResult {
+    pub source: String,
+    pub cm: Arc<SwcSourceMap>,
+    pub src_map_buf: Vec<(BytePos, LineCol)>,
+}
+
+/// The synchronous core of minification: parse, optionally strip types,
+/// minify, and re-emit. Shared by the [`perform_minify`] turbo-tasks function
+/// and the `reduce` bisection tooling, neither of which should duplicate this
+/// pipeline.
+pub(crate) fn minify_source(
+    filename: FileName,
+    source: String,
+    config: &MinifyConfig,
+) -> Result<MinifyResult> {
     let cm = Arc::new(SwcSourceMap::new(FilePathMapping::empty()));
     let compiler = Arc::new(Compiler::new(cm.clone()));
-    let fm = compiler.cm.new_source_file(
-        FileName::Custom((*path.await?.path).to_string()),
-        code.source_code().to_str()?.to_string(),
-    );
+    let fm = compiler.cm.new_source_file(filename.clone(), source);
 
+    let syntax = syntax_for_filename(&filename);
+    let comments = SingleThreadedComments::default();
     let lexer = Lexer::new(
-        Syntax::default(),
+        syntax,
         EsVersion::latest(),
         StringInput::from(&*fm),
-        None,
+        Some(&comments),
     );
     let mut parser = Parser::new_from(lexer);
     let program = try_with_handler(cm.clone(), Default::default(), |handler| {
         GLOBALS.set(&Default::default(), || {
-            let program = parser.parse_program().unwrap();
-            let comments = SingleThreadedComments::default();
+            let program = parser
+                .parse_program()
+                .map_err(|err| anyhow::anyhow!("{err:?}"))?;
             let unresolved_mark = Mark::new();
             let top_level_mark = Mark::new();
 
@@ -77,14 +223,32 @@ async fn perform_minify(path: Vc<FileSystemPath>, code_vc: Vc<Code>) -> Result<V
                         false,
                     ));
 
+                if syntax.typescript() {
+                    program = program.fold_with(&mut swc_core::ecma::transforms::typescript::strip(
+                        top_level_mark,
+                    ));
+                }
+
                 program = swc_core::ecma::minifier::optimize(
                     program,
                     cm.clone(),
                     Some(&comments),
                     None,
                     &MinifyOptions {
-                        compress: Some(Default::default()),
-                        mangle: Some(Default::default()),
+                        compress: Some(CompressOptions {
+                            drop_console: config.drop_console,
+                            drop_debugger: config.drop_debugger,
+                            passes: config.compress_passes,
+                            ..Default::default()
+                        }),
+                        mangle: config.mangle.then(|| MangleOptions {
+                            reserved: config
+                                .keep_names
+                                .iter()
+                                .map(|name| name.as_ref().into())
+                                .collect(),
+                            ..Default::default()
+                        }),
                         ..Default::default()
                     },
                     &ExtraOptions {
@@ -100,25 +264,60 @@ async fn perform_minify(path: Vc<FileSystemPath>, code_vc: Vc<Code>) -> Result<V
         })
     })?;
 
-    let (src, src_map_buf) = print_program(cm.clone(), program)?;
+    let (source, src_map_buf) = print_program(
+        cm.clone(),
+        program,
+        config.target,
+        &comments,
+        config.comments,
+    )?;
 
-    let mut builder = CodeBuilder::default();
-    builder.push_source(
-        &src.into(),
-        Some(*Box::new(Vc::upcast(
-            ParseResultSourceMap::new(cm, src_map_buf).cell(),
-        ))),
-    );
+    Ok(MinifyResult {
+        source,
+        cm,
+        src_map_buf,
+    })
+}
 
-    Ok(builder.build().cell())
+/// Picks the parser [`Syntax`] based on a file's extension, so TypeScript and
+/// JSX sources can be minified directly instead of requiring a prior
+/// transpile step.
+pub(crate) fn syntax_for_filename(filename: &FileName) -> Syntax {
+    // `FileName`'s `Display` wraps custom names in `<...>`, so read the name
+    // from the variant itself.
+    let name = match filename {
+        FileName::Custom(name) => name.clone(),
+        other => other.to_string(),
+    };
+    let extension = name
+        .rsplit('.')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    match extension.as_str() {
+        "ts" | "mts" | "cts" => Syntax::Typescript(TsSyntax::default()),
+        "tsx" => Syntax::Typescript(TsSyntax {
+            tsx: true,
+            ..Default::default()
+        }),
+        "jsx" => Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+        }),
+        _ => Syntax::default(),
+    }
 }
 
 // From https://github.com/swc-project/swc/blob/11efd4e7c5e8081f8af141099d3459c3534c1e1d/crates/swc/src/lib.rs#L523-L560
-fn print_program(
+pub(crate) fn print_program(
     cm: Arc<SwcSourceMap>,
     program: Program,
+    target: EsVersion,
+    comments: &SingleThreadedComments,
+    comments_mode: MinifyCommentsConfig,
 ) -> Result<(String, Vec<(BytePos, LineCol)>)> {
     let mut src_map_buf = vec![];
+    let kept_comments = filtered_comments(comments, comments_mode);
 
     let src = {
         let mut buf = vec![];
@@ -131,11 +330,10 @@ fn print_program(
             )))) as Box<dyn WriteJs>;
 
             let mut emitter = Emitter {
-                cfg: swc_core::ecma::codegen::Config {
-                    minify: true,
-                    ..Default::default()
-                },
-                comments: None,
+                cfg: CodegenConfig::default()
+                    .with_target(target)
+                    .with_minify(true),
+                comments: Some(&kept_comments as &dyn Comments),
                 cm: cm.clone(),
                 wr,
             };
@@ -150,3 +348,101 @@ fn print_program(
 
     Ok((src, src_map_buf))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syntax_for_extension(extension: &str) -> Syntax {
+        syntax_for_filename(&FileName::Custom(format!("test.{extension}")))
+    }
+
+    #[test]
+    fn ts_and_mts_and_cts_parse_as_non_tsx_typescript() {
+        for extension in ["ts", "mts", "cts"] {
+            match syntax_for_extension(extension) {
+                Syntax::Typescript(ts) => assert!(!ts.tsx, "{extension} should not enable tsx"),
+                other => panic!("{extension} should parse as TypeScript, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn tsx_parses_as_tsx_typescript() {
+        match syntax_for_extension("tsx") {
+            Syntax::Typescript(ts) => assert!(ts.tsx),
+            other => panic!("tsx should parse as TypeScript, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn jsx_parses_as_jsx_es() {
+        match syntax_for_extension("jsx") {
+            Syntax::Es(es) => assert!(es.jsx),
+            other => panic!("jsx should parse as Es, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn other_extensions_parse_as_plain_es() {
+        for extension in ["js", "mjs", "cjs"] {
+            assert_eq!(syntax_for_extension(extension), Syntax::default());
+        }
+    }
+
+    fn comment(text: &str) -> Comment {
+        Comment {
+            kind: swc_core::common::comments::CommentKind::Block,
+            span: swc_core::common::DUMMY_SP,
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn should_keep_comment_none_strips_everything() {
+        assert!(!should_keep_comment(
+            &comment("! license"),
+            MinifyCommentsConfig::None
+        ));
+    }
+
+    #[test]
+    fn should_keep_comment_all_keeps_everything() {
+        assert!(should_keep_comment(
+            &comment("just a regular comment"),
+            MinifyCommentsConfig::All
+        ));
+    }
+
+    #[test]
+    fn should_keep_comment_some_keeps_only_legal_comments() {
+        let mode = MinifyCommentsConfig::Some;
+        assert!(should_keep_comment(&comment("! a bang comment"), mode));
+        assert!(should_keep_comment(&comment("@license MIT"), mode));
+        assert!(should_keep_comment(&comment("@preserve keep me"), mode));
+        assert!(!should_keep_comment(&comment("just a regular comment"), mode));
+    }
+
+    #[test]
+    fn filtered_comments_none_produces_no_comments() {
+        let comments = SingleThreadedComments::default();
+        comments.add_leading(BytePos(1), comment("@license MIT"));
+
+        let filtered = filtered_comments(&comments, MinifyCommentsConfig::None);
+
+        assert!(filtered.get_leading(BytePos(1)).is_none());
+    }
+
+    #[test]
+    fn filtered_comments_some_keeps_only_legal_comments() {
+        let comments = SingleThreadedComments::default();
+        comments.add_leading(BytePos(1), comment("@license MIT"));
+        comments.add_leading(BytePos(1), comment("just a regular comment"));
+
+        let filtered = filtered_comments(&comments, MinifyCommentsConfig::Some);
+        let kept = filtered.get_leading(BytePos(1)).unwrap_or_default();
+
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].text.as_ref(), "@license MIT");
+    }
+}
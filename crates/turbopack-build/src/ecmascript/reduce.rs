@@ -0,0 +1,282 @@
+//! A bisection/delta-reduction tool for minification regressions.
+//!
+//! When minified output misbehaves, [`reduce`] localizes the fault: it
+//! delta-reduces the input program down to the smallest set of top-level
+//! items that still reproduces a caller-supplied predicate, and separately
+//! bisects [`MinifyConfig`]'s optimization flags down to the smallest subset
+//! that still reproduces it. Mirrors dbg-swc's reduction tooling.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use swc_core::{
+    common::{FileName, FilePathMapping, SourceMap as SwcSourceMap},
+    ecma::{
+        ast::Program,
+        codegen::{text_writer::JsWriter, Emitter, Node},
+        parser::{lexer::Lexer, Parser, StringInput},
+    },
+};
+
+use super::minify::{minify_source, syntax_for_filename, MinifyConfig};
+
+/// A toggleable optimization flag that the bisector may disable while
+/// searching for a minimal reproduction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flag {
+    Mangle,
+    DropConsole,
+    DropDebugger,
+}
+
+const ALL_FLAGS: [Flag; 3] = [Flag::Mangle, Flag::DropConsole, Flag::DropDebugger];
+
+impl Flag {
+    fn name(self) -> &'static str {
+        match self {
+            Flag::Mangle => "mangle",
+            Flag::DropConsole => "drop_console",
+            Flag::DropDebugger => "drop_debugger",
+        }
+    }
+
+    fn disable(self, config: &mut MinifyConfig) {
+        match self {
+            Flag::Mangle => config.mangle = false,
+            Flag::DropConsole => config.drop_console = false,
+            Flag::DropDebugger => config.drop_debugger = false,
+        }
+    }
+}
+
+/// The minimal reproduction [`reduce`] found.
+pub struct ReducedCase {
+    /// The delta-reduced source that still reproduces the predicate.
+    pub source: String,
+    /// The smallest set of flag names (from `base_config`) that still
+    /// reproduces the predicate when every other flag is disabled.
+    pub minimal_flags: Vec<&'static str>,
+}
+
+/// Runs `predicate` against `minify_source`'s output for `source`/`config`,
+/// treating a parse/minify failure as "does not reproduce" so a bad
+/// reduction step is never mistaken for a hit.
+fn reproduces(
+    filename: &FileName,
+    source: &str,
+    config: &MinifyConfig,
+    predicate: &mut impl FnMut(&str) -> bool,
+) -> bool {
+    match minify_source(filename.clone(), source.to_string(), config) {
+        Ok(result) => predicate(&result.source),
+        Err(_) => false,
+    }
+}
+
+/// Greedily clears flags one at a time, keeping a flag disabled only if the
+/// predicate still reproduces without it, and repeats over the still-enabled
+/// flags until a full round clears none. Starting from the full set and
+/// only ever removing flags that don't matter converges to a minimal subset,
+/// the same guarantee a binary search over the flag powerset would give,
+/// without the extra bookkeeping three flags don't warrant.
+fn minimize_flags(
+    filename: &FileName,
+    source: &str,
+    base_config: &MinifyConfig,
+    predicate: &mut impl FnMut(&str) -> bool,
+) -> MinifyConfig {
+    let mut config = base_config.clone();
+    loop {
+        let mut cleared_any = false;
+        for flag in ALL_FLAGS {
+            let enabled = match flag {
+                Flag::Mangle => config.mangle,
+                Flag::DropConsole => config.drop_console,
+                Flag::DropDebugger => config.drop_debugger,
+            };
+            if !enabled {
+                continue;
+            }
+            let mut candidate = config.clone();
+            flag.disable(&mut candidate);
+            if reproduces(filename, source, &candidate, predicate) {
+                config = candidate;
+                cleared_any = true;
+            }
+        }
+        if !cleared_any {
+            break;
+        }
+    }
+    config
+}
+
+fn parse_program(cm: &Arc<SwcSourceMap>, filename: FileName, source: &str) -> Result<Program> {
+    let fm = cm.new_source_file(filename.clone(), source.to_string());
+    let syntax = syntax_for_filename(&filename);
+    let lexer = Lexer::new(syntax, Default::default(), StringInput::from(&*fm), None);
+    let mut parser = Parser::new_from(lexer);
+    parser
+        .parse_program()
+        .map_err(|err| anyhow::anyhow!("{err:?}"))
+}
+
+fn print_plain(cm: &Arc<SwcSourceMap>, program: &Program) -> Result<String> {
+    let mut buf = vec![];
+    {
+        let wr = JsWriter::new(cm.clone(), "\n", &mut buf, None);
+        let mut emitter = Emitter {
+            cfg: Default::default(),
+            comments: None,
+            cm: cm.clone(),
+            wr: Box::new(wr),
+        };
+        program.emit_with(&mut emitter)?;
+    }
+    Ok(String::from_utf8(buf)?)
+}
+
+fn top_level_len(program: &Program) -> usize {
+    match program {
+        Program::Module(module) => module.body.len(),
+        Program::Script(script) => script.body.len(),
+    }
+}
+
+fn without_top_level_item(program: &Program, index: usize) -> Program {
+    let mut program = program.clone();
+    match &mut program {
+        Program::Module(module) => {
+            module.body.remove(index);
+        }
+        Program::Script(script) => {
+            script.body.remove(index);
+        }
+    }
+    program
+}
+
+/// Greedily removes one top-level statement/declaration at a time, keeping
+/// the removal only if the predicate still reproduces, until no further
+/// removal shrinks the reproduction. This is the delta-reduction fixpoint
+/// described in the module docs.
+fn delta_reduce(
+    filename: &FileName,
+    source: &str,
+    config: &MinifyConfig,
+    predicate: &mut impl FnMut(&str) -> bool,
+) -> Result<String> {
+    let cm = Arc::new(SwcSourceMap::new(FilePathMapping::empty()));
+    let mut program = parse_program(&cm, filename.clone(), source)?;
+
+    loop {
+        let mut removed_any = false;
+        let mut index = 0;
+        while index < top_level_len(&program) {
+            let candidate = without_top_level_item(&program, index);
+            let candidate_source = print_plain(&cm, &candidate)?;
+            if reproduces(filename, &candidate_source, config, predicate) {
+                program = candidate;
+                removed_any = true;
+                // Don't advance: the next item has shifted down into `index`.
+            } else {
+                index += 1;
+            }
+        }
+        if !removed_any {
+            break;
+        }
+    }
+
+    print_plain(&cm, &program)
+}
+
+/// Given a source file, a starting [`MinifyConfig`], and a predicate
+/// describing the observed bug (e.g. "output still parses" or "output no
+/// longer contains symbol X"), produces a minimal reproduction: the smallest
+/// input that still reproduces the predicate under the smallest flag set
+/// that still reproduces it.
+pub fn reduce(
+    filename: FileName,
+    source: &str,
+    base_config: &MinifyConfig,
+    mut predicate: impl FnMut(&str) -> bool,
+) -> Result<ReducedCase> {
+    anyhow::ensure!(
+        reproduces(&filename, source, base_config, &mut predicate),
+        "predicate does not reproduce on the unreduced input; nothing to reduce"
+    );
+
+    let minimal_config = minimize_flags(&filename, source, base_config, &mut predicate);
+    let reduced_source = delta_reduce(&filename, source, &minimal_config, &mut predicate)?;
+
+    let minimal_flags = ALL_FLAGS
+        .into_iter()
+        .filter(|flag| match flag {
+            Flag::Mangle => minimal_config.mangle,
+            Flag::DropConsole => minimal_config.drop_console,
+            Flag::DropDebugger => minimal_config.drop_debugger,
+        })
+        .map(Flag::name)
+        .collect();
+
+    Ok(ReducedCase {
+        source: reduced_source,
+        minimal_flags,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filename() -> FileName {
+        FileName::Custom("test.js".into())
+    }
+
+    #[test]
+    fn delta_reduce_drops_unrelated_top_level_items() {
+        let source = "function unrelatedA() { return 1; }\n\
+                       function unrelatedB() { return 2; }\n\
+                       var marker = 'bug-marker';\n";
+        let config = MinifyConfig::default();
+
+        let reduced =
+            delta_reduce(&filename(), source, &config, &mut |output: &str| {
+                output.contains("bug-marker")
+            })
+            .unwrap();
+
+        assert!(reduced.contains("bug-marker"));
+        assert!(!reduced.contains("unrelatedA"));
+        assert!(!reduced.contains("unrelatedB"));
+    }
+
+    #[test]
+    fn minimize_flags_disables_flags_the_predicate_does_not_need() {
+        let source = "debugger;\nvar marker = 1;\n";
+        let base_config = MinifyConfig {
+            drop_debugger: true,
+            ..MinifyConfig::default()
+        };
+
+        let minimal = minimize_flags(&filename(), source, &base_config, &mut |output: &str| {
+            output.contains("debugger")
+        });
+
+        // Keeping the `debugger` statement in the output requires
+        // `drop_debugger` to be off; the other flags are irrelevant here.
+        assert!(!minimal.drop_debugger);
+    }
+
+    #[test]
+    fn reduce_produces_minimal_reproduction() {
+        let source = "function noise() { return 0; }\nvar marker = 'bug';\n";
+        let config = MinifyConfig::default();
+
+        let result = reduce(filename(), source, &config, |output| output.contains("bug")).unwrap();
+
+        assert!(result.source.contains("bug"));
+        assert!(!result.source.contains("noise"));
+    }
+}